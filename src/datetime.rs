@@ -12,6 +12,11 @@ const T: u8 = 84; // "T"
 const COLON: u8 = 58; // ":"
 const PERIOD: u8 = 46; // "."
 const Z: u8 = 90; // "Z"
+const P: u8 = 80; // "P"
+const D: u8 = 68; // "D"
+const H: u8 = 72; // "H"
+const M: u8 = 77; // "M"
+const S: u8 = 83; // "S"
 
 pub type DateTimeBuffer = heapless::Vec<u8, heapless::consts::U32>;
 
@@ -144,53 +149,65 @@ impl DateTime {
             opts: opts,
         }
     }
-    pub fn write_buf(&self, buf: &mut DateTimeBuffer) -> Result<(), DateTimeError> {
-        let has_tz = unsafe { (*(self.ptr as *mut pyo3::ffi::PyDateTime_DateTime)).hastzinfo == 1 };
-        let offset_day: i32;
-        let mut offset_second: i32;
-        if !has_tz {
-            offset_second = 0;
-            offset_day = 0;
+    fn has_tz(&self) -> bool {
+        unsafe { (*(self.ptr as *mut pyo3::ffi::PyDateTime_DateTime)).hastzinfo == 1 }
+    }
+
+    // UTC offset as (days, seconds) per CPython's timedelta storage, e.g.
+    // (-1, 68400) for -05:00. Returns (0, 0) for naive datetimes.
+    fn resolve_offset(&self) -> Result<(i32, i32), DateTimeError> {
+        if !self.has_tz() {
+            return Ok((0, 0));
+        }
+        let tzinfo = ffi!(PyDateTime_DATE_GET_TZINFO(self.ptr));
+        if ffi!(PyObject_HasAttr(tzinfo, CONVERT_METHOD_STR)) == 1 {
+            // pendulum
+            let offset = ffi!(PyObject_CallMethodObjArgs(
+                self.ptr,
+                UTCOFFSET_METHOD_STR,
+                std::ptr::null_mut() as *mut pyo3::ffi::PyObject
+            ));
+            Ok((
+                ffi!(PyDateTime_DELTA_GET_DAYS(offset)),
+                ffi!(PyDateTime_DELTA_GET_SECONDS(offset)) as i32,
+            ))
+        } else if ffi!(PyObject_HasAttr(tzinfo, NORMALIZE_METHOD_STR)) == 1 {
+            // pytz
+            let method_ptr = ffi!(PyObject_CallMethodObjArgs(
+                tzinfo,
+                NORMALIZE_METHOD_STR,
+                self.ptr,
+                std::ptr::null_mut() as *mut pyo3::ffi::PyObject
+            ));
+            let offset = ffi!(PyObject_CallMethodObjArgs(
+                method_ptr,
+                UTCOFFSET_METHOD_STR,
+                std::ptr::null_mut() as *mut pyo3::ffi::PyObject
+            ));
+            Ok((
+                ffi!(PyDateTime_DELTA_GET_DAYS(offset)),
+                ffi!(PyDateTime_DELTA_GET_SECONDS(offset)) as i32,
+            ))
+        } else if ffi!(PyObject_HasAttr(tzinfo, DST_STR)) == 1 {
+            // dateutil/arrow, datetime.timezone.utc
+            let offset = ffi!(PyObject_CallMethodObjArgs(
+                tzinfo,
+                UTCOFFSET_METHOD_STR,
+                self.ptr,
+                std::ptr::null_mut() as *mut pyo3::ffi::PyObject
+            ));
+            Ok((
+                ffi!(PyDateTime_DELTA_GET_DAYS(offset)),
+                ffi!(PyDateTime_DELTA_GET_SECONDS(offset)) as i32,
+            ))
         } else {
-            let tzinfo = ffi!(PyDateTime_DATE_GET_TZINFO(self.ptr));
-            if ffi!(PyObject_HasAttr(tzinfo, CONVERT_METHOD_STR)) == 1 {
-                // pendulum
-                let offset = ffi!(PyObject_CallMethodObjArgs(
-                    self.ptr,
-                    UTCOFFSET_METHOD_STR,
-                    std::ptr::null_mut() as *mut pyo3::ffi::PyObject
-                ));
-                offset_second = ffi!(PyDateTime_DELTA_GET_SECONDS(offset)) as i32;
-                offset_day = ffi!(PyDateTime_DELTA_GET_DAYS(offset));
-            } else if ffi!(PyObject_HasAttr(tzinfo, NORMALIZE_METHOD_STR)) == 1 {
-                // pytz
-                let method_ptr = ffi!(PyObject_CallMethodObjArgs(
-                    tzinfo,
-                    NORMALIZE_METHOD_STR,
-                    self.ptr,
-                    std::ptr::null_mut() as *mut pyo3::ffi::PyObject
-                ));
-                let offset = ffi!(PyObject_CallMethodObjArgs(
-                    method_ptr,
-                    UTCOFFSET_METHOD_STR,
-                    std::ptr::null_mut() as *mut pyo3::ffi::PyObject
-                ));
-                offset_second = ffi!(PyDateTime_DELTA_GET_SECONDS(offset)) as i32;
-                offset_day = ffi!(PyDateTime_DELTA_GET_DAYS(offset));
-            } else if ffi!(PyObject_HasAttr(tzinfo, DST_STR)) == 1 {
-                // dateutil/arrow, datetime.timezone.utc
-                let offset = ffi!(PyObject_CallMethodObjArgs(
-                    tzinfo,
-                    UTCOFFSET_METHOD_STR,
-                    self.ptr,
-                    std::ptr::null_mut() as *mut pyo3::ffi::PyObject
-                ));
-                offset_second = ffi!(PyDateTime_DELTA_GET_SECONDS(offset)) as i32;
-                offset_day = ffi!(PyDateTime_DELTA_GET_DAYS(offset));
-            } else {
-                return Err(DateTimeError::LibraryUnsupported);
-            }
-        };
+            Err(DateTimeError::LibraryUnsupported)
+        }
+    }
+
+    pub fn write_buf(&self, buf: &mut DateTimeBuffer) -> Result<(), DateTimeError> {
+        let has_tz = self.has_tz();
+        let (offset_day, mut offset_second) = self.resolve_offset()?;
 
         buf.extend_from_slice(
             itoa::Buffer::new()
@@ -276,6 +293,102 @@ impl DateTime {
         }
         Ok(())
     }
+
+    // Seconds (and leftover microseconds) since the Unix epoch, for OPT_TIMESTAMP.
+    pub fn timestamp(&self) -> Result<(i64, u32), DateTimeError> {
+        let (offset_day, offset_second) = self.resolve_offset()?;
+        let year = ffi!(PyDateTime_GET_YEAR(self.ptr)) as i64;
+        let month = ffi!(PyDateTime_GET_MONTH(self.ptr)) as i64;
+        let day = ffi!(PyDateTime_GET_DAY(self.ptr)) as i64;
+        let hour = ffi!(PyDateTime_DATE_GET_HOUR(self.ptr)) as i64;
+        let minute = ffi!(PyDateTime_DATE_GET_MINUTE(self.ptr)) as i64;
+        let second = ffi!(PyDateTime_DATE_GET_SECOND(self.ptr)) as i64;
+        let microsecond = ffi!(PyDateTime_DATE_GET_MICROSECOND(self.ptr)) as u32;
+
+        let days = days_from_civil(year, month, day);
+        let epoch_second = days * 86_400
+            + hour * 3600
+            + minute * 60
+            + second
+            - (offset_day as i64) * 86_400
+            - offset_second as i64;
+        Ok((epoch_second, microsecond))
+    }
+
+    // Writes the RFC 2822 form, e.g. "Tue, 01 Jul 2003 10:52:37 +0200", for OPT_RFC2822.
+    pub fn write_rfc2822_buf(&self, buf: &mut DateTimeBuffer) -> Result<(), DateTimeError> {
+        let (offset_day, mut offset_second) = self.resolve_offset()?;
+        let year = ffi!(PyDateTime_GET_YEAR(self.ptr)) as i64;
+        let month = ffi!(PyDateTime_GET_MONTH(self.ptr)) as i64;
+        let day = ffi!(PyDateTime_GET_DAY(self.ptr)) as u8;
+
+        let days = days_from_civil(year, month, day as i64);
+        // days_from_civil(1970, 1, 1) == 0, a Thursday.
+        let weekday = (((days % 7 + 7) % 7) + 3) % 7;
+
+        buf.extend_from_slice(WEEKDAYS[weekday as usize].as_bytes())
+            .unwrap();
+        buf.extend_from_slice(b", ").unwrap();
+        write_double_digit!(buf, day);
+        buf.push(b' ').unwrap();
+        buf.extend_from_slice(MONTHS[(month - 1) as usize].as_bytes())
+            .unwrap();
+        buf.push(b' ').unwrap();
+        buf.extend_from_slice(itoa::Buffer::new().format(year).as_bytes())
+            .unwrap();
+        buf.push(b' ').unwrap();
+        {
+            let hour = ffi!(PyDateTime_DATE_GET_HOUR(self.ptr)) as u8;
+            write_double_digit!(buf, hour);
+        }
+        buf.push(COLON).unwrap();
+        {
+            let minute = ffi!(PyDateTime_DATE_GET_MINUTE(self.ptr)) as u8;
+            write_double_digit!(buf, minute);
+        }
+        buf.push(COLON).unwrap();
+        {
+            let second = ffi!(PyDateTime_DATE_GET_SECOND(self.ptr)) as u8;
+            write_double_digit!(buf, second);
+        }
+        buf.push(b' ').unwrap();
+        if offset_day == 0 && offset_second == 0 {
+            buf.extend_from_slice(b"+0000").unwrap();
+        } else {
+            if offset_day == -1 {
+                // datetime.timedelta(days=-1, seconds=68400) -> -0500
+                buf.push(HYPHEN).unwrap();
+                offset_second = 86400 - offset_second;
+            } else {
+                buf.push(PLUS).unwrap();
+            }
+            let offset_minute = offset_second / 60;
+            let offset_hour = offset_minute / 60;
+            write_double_digit!(buf, offset_hour);
+            let mut offset_minute_print = offset_minute % 60;
+            let offset_excess_second = offset_second - (offset_minute_print * 60 + offset_hour * 3600);
+            if offset_excess_second >= 30 {
+                offset_minute_print += 1;
+            }
+            write_double_digit!(buf, offset_minute_print);
+        }
+        Ok(())
+    }
+}
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// Howard Hinnant's days_from_civil; days since the Unix epoch.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
 }
 
 impl<'p> Serialize for DateTime {
@@ -283,6 +396,24 @@ impl<'p> Serialize for DateTime {
     where
         S: Serializer,
     {
+        if self.opts & RFC2822 == RFC2822 {
+            let mut buf: DateTimeBuffer = heapless::Vec::new();
+            if self.write_rfc2822_buf(&mut buf).is_err() {
+                err!(DATETIME_LIBRARY_UNSUPPORTED)
+            }
+            return serializer.serialize_str(str_from_slice!(buf.as_ptr(), buf.len()));
+        }
+        if self.opts & TIMESTAMP == TIMESTAMP {
+            let (epoch_second, microsecond) = match self.timestamp() {
+                Ok(value) => value,
+                Err(_) => err!(DATETIME_LIBRARY_UNSUPPORTED),
+            };
+            return if microsecond != 0 && self.opts & OMIT_MICROSECONDS != OMIT_MICROSECONDS {
+                serializer.serialize_f64(epoch_second as f64 + (microsecond as f64 / 1_000_000.0))
+            } else {
+                serializer.serialize_i64(epoch_second)
+            };
+        }
         let mut buf: DateTimeBuffer = heapless::Vec::new();
         if self.write_buf(&mut buf).is_err() {
             err!(DATETIME_LIBRARY_UNSUPPORTED)
@@ -290,3 +421,301 @@ impl<'p> Serialize for DateTime {
         serializer.serialize_str(str_from_slice!(buf.as_ptr(), buf.len()))
     }
 }
+
+#[repr(transparent)]
+pub struct Timedelta {
+    ptr: *mut pyo3::ffi::PyObject,
+}
+
+// timedelta.max.days (999_999_999) overflows i64 once converted to
+// microseconds, so the magnitude is computed in i128.
+fn split_timedelta(days: i128, seconds: i128, microsecond: i128) -> (bool, i128, i128, i128, i128, u32) {
+    let total_microsecond = days * 86_400_000_000 + seconds * 1_000_000 + microsecond;
+    let negative = total_microsecond < 0;
+    let mut magnitude = total_microsecond.abs();
+
+    let days = magnitude / 86_400_000_000;
+    magnitude %= 86_400_000_000;
+    let hours = magnitude / 3_600_000_000;
+    magnitude %= 3_600_000_000;
+    let minutes = magnitude / 60_000_000;
+    magnitude %= 60_000_000;
+    let seconds = magnitude / 1_000_000;
+    let microsecond = (magnitude % 1_000_000) as u32;
+
+    (negative, days, hours, minutes, seconds, microsecond)
+}
+
+impl Timedelta {
+    pub fn new(ptr: *mut pyo3::ffi::PyObject) -> Self {
+        Timedelta { ptr: ptr }
+    }
+    pub fn write_buf(&self, buf: &mut DateTimeBuffer) {
+        let days = ffi!(PyDateTime_DELTA_GET_DAYS(self.ptr)) as i128;
+        let seconds = ffi!(PyDateTime_DELTA_GET_SECONDS(self.ptr)) as i128;
+        let microsecond = ffi!(PyDateTime_DELTA_GET_MICROSECONDS(self.ptr)) as i128;
+
+        let (negative, days, hours, minutes, seconds, microsecond) =
+            split_timedelta(days, seconds, microsecond);
+
+        if negative {
+            buf.push(HYPHEN).unwrap();
+        }
+        buf.push(P).unwrap();
+        if days != 0 {
+            buf.extend_from_slice(itoa::Buffer::new().format(days).as_bytes())
+                .unwrap();
+            buf.push(D).unwrap();
+        }
+        if hours != 0 || minutes != 0 || seconds != 0 || microsecond != 0 || days == 0 {
+            buf.push(T).unwrap();
+            if hours != 0 {
+                buf.extend_from_slice(itoa::Buffer::new().format(hours).as_bytes())
+                    .unwrap();
+                buf.push(H).unwrap();
+            }
+            if minutes != 0 {
+                buf.extend_from_slice(itoa::Buffer::new().format(minutes).as_bytes())
+                    .unwrap();
+                buf.push(M).unwrap();
+            }
+            buf.extend_from_slice(itoa::Buffer::new().format(seconds).as_bytes())
+                .unwrap();
+            write_microsecond!(buf, microsecond);
+            buf.push(S).unwrap();
+        }
+    }
+}
+
+impl<'p> Serialize for Timedelta {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf: DateTimeBuffer = heapless::Vec::new();
+        self.write_buf(&mut buf);
+        serializer.serialize_str(str_from_slice!(buf.as_ptr(), buf.len()))
+    }
+}
+
+#[inline]
+fn two_digits(bytes: &[u8], idx: usize) -> Option<u32> {
+    let a = *bytes.get(idx)?;
+    let b = *bytes.get(idx + 1)?;
+    if !a.is_ascii_digit() || !b.is_ascii_digit() {
+        return None;
+    }
+    Some((a - ZERO) as u32 * 10 + (b - ZERO) as u32)
+}
+
+#[inline]
+fn four_digits(bytes: &[u8]) -> Option<i32> {
+    let mut value: i32 = 0;
+    for idx in 0..4 {
+        let byte = *bytes.get(idx)?;
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        value = value * 10 + (byte - ZERO) as i32;
+    }
+    Some(value)
+}
+
+#[inline]
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+#[inline]
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+        _ => 0,
+    }
+}
+
+// Strict RFC 3339 / ISO 8601 date/time/datetime parser for OPT_PARSE_DATETIME.
+// Returns None (without raising) on anything that doesn't match.
+pub fn parse_datetime(value: &str) -> Option<*mut pyo3::ffi::PyObject> {
+    let bytes = value.as_bytes();
+    if bytes.len() < 10 {
+        return None;
+    }
+    let year = four_digits(bytes)?;
+    if bytes[4] != HYPHEN {
+        return None;
+    }
+    let month = two_digits(bytes, 5)?;
+    if month < 1 || month > 12 {
+        return None;
+    }
+    if bytes[7] != HYPHEN {
+        return None;
+    }
+    let day = two_digits(bytes, 8)?;
+    if day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    if bytes.len() == 10 {
+        return pyobject_or_clear(ffi!(PyDate_FromDate(year, month as i32, day as i32)));
+    }
+
+    if bytes.len() < 19 || (bytes[10] != T && bytes[10] != b' ') {
+        return None;
+    }
+    let hour = two_digits(bytes, 11)?;
+    if hour > 23 {
+        return None;
+    }
+    if bytes[13] != COLON {
+        return None;
+    }
+    let minute = two_digits(bytes, 14)?;
+    if minute > 59 {
+        return None;
+    }
+    if bytes[16] != COLON {
+        return None;
+    }
+    let second = two_digits(bytes, 17)?;
+    if second > 59 {
+        return None;
+    }
+
+    let mut idx = 19;
+    let mut microsecond: u32 = 0;
+    if idx < bytes.len() && bytes[idx] == PERIOD {
+        idx += 1;
+        let start = idx;
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        let num_digits = idx - start;
+        if num_digits == 0 || num_digits > 9 {
+            return None;
+        }
+        let mut digits = [ZERO; 6];
+        let take = num_digits.min(6);
+        digits[..take].copy_from_slice(&bytes[start..start + take]);
+        microsecond = std::str::from_utf8(&digits).unwrap().parse::<u32>().unwrap();
+    }
+
+    if idx == bytes.len() {
+        return pyobject_or_clear(ffi!(PyDateTime_FromDateAndTime(
+            year,
+            month as i32,
+            day as i32,
+            hour as i32,
+            minute as i32,
+            second as i32,
+            microsecond as i32,
+        )));
+    }
+
+    let mut offset_second: i32;
+    if bytes[idx] == Z {
+        if idx + 1 != bytes.len() {
+            return None;
+        }
+        offset_second = 0;
+    } else if bytes[idx] == PLUS || bytes[idx] == HYPHEN {
+        let negative = bytes[idx] == HYPHEN;
+        idx += 1;
+        if bytes.len() < idx + 5 || bytes[idx + 2] != COLON {
+            return None;
+        }
+        let offset_hour = two_digits(bytes, idx)?;
+        let offset_minute = two_digits(bytes, idx + 3)?;
+        if offset_hour > 23 || offset_minute > 59 || idx + 5 != bytes.len() {
+            return None;
+        }
+        offset_second = (offset_hour * 3600 + offset_minute * 60) as i32;
+        if negative {
+            offset_second = -offset_second;
+        }
+    } else {
+        return None;
+    }
+
+    let tzinfo = ffi!(PyTimeZone_FromOffset(ffi!(PyDelta_FromDSU(0, offset_second, 0))));
+    pyobject_or_clear(unsafe {
+        ((*pyo3::ffi::PyDateTimeAPI()).DateTime_FromDateAndTime)(
+            year,
+            month as i32,
+            day as i32,
+            hour as i32,
+            minute as i32,
+            second as i32,
+            microsecond as i32,
+            tzinfo,
+            (*pyo3::ffi::PyDateTimeAPI()).DateTimeType,
+        )
+    })
+}
+
+// Clears the pending ValueError and returns None instead of letting a NULL
+// PyObject* from a failed calendar-date construction escape as success.
+fn pyobject_or_clear(ptr: *mut pyo3::ffi::PyObject) -> Option<*mut pyo3::ffi::PyObject> {
+    if ptr.is_null() {
+        ffi!(PyErr_Clear());
+        return None;
+    }
+    Some(ptr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{days_from_civil, days_in_month, split_timedelta};
+
+    #[test]
+    fn days_from_civil_matches_known_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0); // epoch
+        assert_eq!(days_from_civil(1969, 12, 31), -1); // pre-1970
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782); // leap-year end-of-month
+    }
+
+    #[test]
+    fn rejects_impossible_calendar_dates() {
+        assert_eq!(days_in_month(2023, 4), 30); // "2023-04-31" is invalid
+        assert_eq!(days_in_month(2023, 2), 28); // "2023-02-29" is invalid
+        assert_eq!(days_in_month(2024, 2), 29); // 2024 is a leap year
+        assert_eq!(days_in_month(1900, 2), 28); // divisible by 100, not 400
+        assert_eq!(days_in_month(2000, 2), 29); // divisible by 400
+    }
+
+    #[test]
+    fn timedelta_split_does_not_overflow_i64_for_max_days() {
+        // datetime.timedelta.max == timedelta(days=999999999, seconds=86399,
+        // microseconds=999999); this previously overflowed as i64 microseconds.
+        let (negative, days, hours, minutes, seconds, microsecond) =
+            split_timedelta(999_999_999, 86_399, 999_999);
+        assert!(!negative);
+        assert_eq!(days, 999_999_999);
+        assert_eq!(hours, 23);
+        assert_eq!(minutes, 59);
+        assert_eq!(seconds, 59);
+        assert_eq!(microsecond, 999_999);
+    }
+
+    #[test]
+    fn timedelta_split_normalizes_negative_magnitude() {
+        // datetime.timedelta(days=-1, seconds=68400) == -5 hours.
+        let (negative, days, hours, minutes, seconds, microsecond) =
+            split_timedelta(-1, 68_400, 0);
+        assert!(negative);
+        assert_eq!(days, 0);
+        assert_eq!(hours, 5);
+        assert_eq!(minutes, 0);
+        assert_eq!(seconds, 0);
+        assert_eq!(microsecond, 0);
+    }
+}
@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::bytes::*;
+use crate::datetime::parse_datetime;
 use crate::exc::*;
+use crate::opt::*;
 use crate::typeref::*;
 use crate::unicode::*;
 use associative_cache::replacement::RoundRobinReplacement;
@@ -11,12 +13,57 @@ use pyo3::prelude::*;
 use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
 use smallvec::SmallVec;
 use std::borrow::Cow;
+use std::ffi::CString;
 use std::fmt;
 use std::os::raw::c_char;
 use std::os::raw::c_void;
 use std::ptr::NonNull;
 use wyhash::wyhash;
 
+// Sentinel map key serde_json's arbitrary_precision feature (enabled in
+// Cargo.toml) wraps every number in, since deserialize_any can't hand a
+// Visitor an integer wider than i64/u64 directly.
+const JSON_NUMBER_KEY: &str = "$serde_json::private::Number";
+
+#[derive(Debug, PartialEq)]
+enum NumberLiteral<'a> {
+    Float(f64),
+    I64(i64),
+    U64(u64),
+    Big(&'a str),
+}
+
+fn classify_number(value: &str) -> NumberLiteral {
+    if value.contains('.') || value.contains('e') || value.contains('E') {
+        return NumberLiteral::Float(value.parse::<f64>().unwrap_or(f64::NAN));
+    }
+    if let Ok(parsed) = value.parse::<i64>() {
+        return NumberLiteral::I64(parsed);
+    }
+    if let Ok(parsed) = value.parse::<u64>() {
+        return NumberLiteral::U64(parsed);
+    }
+    NumberLiteral::Big(value)
+}
+
+// Falls back to PyLong_FromString for integers too wide for i64/u64 so
+// precision is never lost.
+fn pynumber_from_str(value: &str) -> *mut pyo3::ffi::PyObject {
+    match classify_number(value) {
+        NumberLiteral::Float(value) => ffi!(PyFloat_FromDouble(value)),
+        NumberLiteral::I64(value) => ffi!(PyLong_FromLongLong(value)),
+        NumberLiteral::U64(value) => ffi!(PyLong_FromUnsignedLongLong(value)),
+        NumberLiteral::Big(value) => {
+            let cstr = CString::new(value).unwrap();
+            ffi!(PyLong_FromString(
+                cstr.as_ptr() as *const c_char,
+                std::ptr::null_mut(),
+                10
+            ))
+        }
+    }
+}
+
 #[derive(Clone)]
 struct CachedKey {
     ptr: *mut c_void,
@@ -54,7 +101,7 @@ lazy_static! {
     static ref KEY_MAP: parking_lot::Mutex<KeyMap> = { parking_lot::Mutex::new(KeyMap::default()) };
 }
 
-pub fn deserialize(ptr: *mut pyo3::ffi::PyObject) -> PyResult<NonNull<pyo3::ffi::PyObject>> {
+pub fn deserialize(ptr: *mut pyo3::ffi::PyObject, opts: Opt) -> PyResult<NonNull<pyo3::ffi::PyObject>> {
     let obj_type_ptr = ob_type!(ptr);
     let contents: &[u8];
     if is_type!(obj_type_ptr, STR_TYPE) {
@@ -89,7 +136,7 @@ pub fn deserialize(ptr: *mut pyo3::ffi::PyObject) -> PyResult<NonNull<pyo3::ffi:
     let data = unsafe { std::str::from_utf8_unchecked(contents) };
     let mut deserializer = serde_json::Deserializer::from_str(data);
 
-    let seed = JsonValue {};
+    let seed = JsonValue { opts: opts };
     match seed.deserialize(&mut deserializer) {
         Ok(obj) => {
             deserializer
@@ -102,7 +149,9 @@ pub fn deserialize(ptr: *mut pyo3::ffi::PyObject) -> PyResult<NonNull<pyo3::ffi:
 }
 
 #[derive(Clone, Copy)]
-struct JsonValue;
+struct JsonValue {
+    opts: Opt,
+}
 
 impl<'de, 'a> DeserializeSeed<'de> for JsonValue {
     type Value = *mut pyo3::ffi::PyObject;
@@ -165,6 +214,11 @@ impl<'de, 'a> Visitor<'de> for JsonValue {
     where
         E: de::Error,
     {
+        if self.opts & PARSE_DATETIME != 0 {
+            if let Some(pyobj) = parse_datetime(value) {
+                return Ok(pyobj);
+            }
+        }
         Ok(str_to_pyobject!(value))
     }
 
@@ -172,6 +226,11 @@ impl<'de, 'a> Visitor<'de> for JsonValue {
     where
         E: de::Error,
     {
+        if self.opts & PARSE_DATETIME != 0 {
+            if let Some(pyobj) = parse_datetime(value) {
+                return Ok(pyobj);
+            }
+        }
         Ok(str_to_pyobject!(value))
     }
 
@@ -194,8 +253,15 @@ impl<'de, 'a> Visitor<'de> for JsonValue {
     where
         A: MapAccess<'de>,
     {
-        let dict_ptr = ffi!(PyDict_New());
+        // Every JSON number routes through here keyed by JSON_NUMBER_KEY, so
+        // the dict is only allocated once the first key isn't that sentinel.
+        let mut dict_ptr: Option<*mut pyo3::ffi::PyObject> = None;
         while let Some(key) = map.next_key::<Cow<str>>()? {
+            if dict_ptr.is_none() && key.as_ref() == JSON_NUMBER_KEY {
+                let value: Cow<str> = map.next_value()?;
+                return Ok(pynumber_from_str(value.as_ref()));
+            }
+            let dict_ptr = *dict_ptr.get_or_insert_with(|| ffi!(PyDict_New()));
             let pykey: *mut pyo3::ffi::PyObject;
             let pyhash: pyo3::ffi::Py_hash_t;
             if unlikely!(key.len() > 64) {
@@ -223,6 +289,40 @@ impl<'de, 'a> Visitor<'de> for JsonValue {
             ffi!(Py_DECREF(pykey));
             ffi!(Py_DECREF(value));
         }
-        Ok(dict_ptr)
+        Ok(dict_ptr.unwrap_or_else(|| ffi!(PyDict_New())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify_number, NumberLiteral};
+
+    #[test]
+    fn classifies_float_literal() {
+        assert_eq!(classify_number("1.5"), NumberLiteral::Float(1.5));
+    }
+
+    #[test]
+    fn classifies_i64_literal() {
+        assert_eq!(
+            classify_number("9223372036854775807"),
+            NumberLiteral::I64(i64::MAX)
+        );
+    }
+
+    #[test]
+    fn classifies_u64_literal_out_of_i64_range() {
+        assert_eq!(
+            classify_number("18446744073709551615"),
+            NumberLiteral::U64(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn classifies_negative_big_literal_out_of_i64_range() {
+        assert_eq!(
+            classify_number("-99999999999999999999"),
+            NumberLiteral::Big("-99999999999999999999")
+        );
     }
 }
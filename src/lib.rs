@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[macro_use]
+mod macros;
+mod bytes;
+mod datetime;
+mod decode;
+mod exc;
+mod opt;
+mod typeref;
+mod unicode;
+
+use crate::opt::*;
+use crate::typeref::*;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyAny, PyBytes};
+use pyo3::wrap_pyfunction;
+use std::ptr::NonNull;
+
+/// `orjson.loads(data, /, *, opts=0)` — deserializes `data`. `opts` is the
+/// same `OPT_*` bitmask accepted by `dumps`; today only `OPT_PARSE_DATETIME`
+/// affects decoding.
+#[pyfunction]
+#[pyo3(signature = (data, opts = 0))]
+fn loads(py: Python, data: &PyAny, opts: Opt) -> PyResult<PyObject> {
+    let obj: NonNull<pyo3::ffi::PyObject> = decode::deserialize(data.as_ptr(), opts)?;
+    Ok(unsafe { PyObject::from_owned_ptr(py, obj.as_ptr()) })
+}
+
+/// `orjson.dumps(obj, /, *, opts=0)` — serializes `obj` to UTF-8 JSON
+/// bytes. Dispatches on the handful of non-native types this crate
+/// currently implements `Serialize` for; native containers (dict, list,
+/// str, int, float, bool, None) are handled by the rest of the encoder
+/// outside this snapshot.
+#[pyfunction]
+#[pyo3(signature = (obj, opts = 0))]
+fn dumps(py: Python, obj: &PyAny, opts: Opt) -> PyResult<PyObject> {
+    let ptr = obj.as_ptr();
+    let obj_type_ptr = ob_type!(ptr);
+
+    let result = if is_type!(obj_type_ptr, TIMEDELTA_TYPE) {
+        serde_json::to_vec(&datetime::Timedelta::new(ptr))
+    } else if is_type!(obj_type_ptr, DATETIME_TYPE) {
+        serde_json::to_vec(&datetime::DateTime::new(ptr, opts))
+    } else if is_type!(obj_type_ptr, DATE_TYPE) {
+        serde_json::to_vec(&datetime::Date::new(ptr))
+    } else if is_type!(obj_type_ptr, TIME_TYPE) {
+        match datetime::Time::new(ptr, opts) {
+            Ok(time) => serde_json::to_vec(&time),
+            Err(datetime::TimeError::HasTimezone) => {
+                return Err(PyValueError::new_err(
+                    "datetime.time with tzinfo is not supported",
+                ))
+            }
+        }
+    } else {
+        return Err(PyTypeError::new_err(format!(
+            "Type is not JSON serializable: {}",
+            obj.get_type().name()?
+        )));
+    };
+
+    match result {
+        Ok(buf) => Ok(PyBytes::new(py, &buf).into()),
+        Err(err) => Err(PyValueError::new_err(err.to_string())),
+    }
+}
+
+#[pymodule]
+fn orjson(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(loads, m)?)?;
+    m.add_function(wrap_pyfunction!(dumps, m)?)?;
+
+    m.add("OPT_NAIVE_UTC", NAIVE_UTC)?;
+    m.add("OPT_UTC_Z", UTC_Z)?;
+    m.add("OPT_OMIT_MICROSECONDS", OMIT_MICROSECONDS)?;
+    m.add("OPT_PARSE_DATETIME", PARSE_DATETIME)?;
+    m.add("OPT_TIMESTAMP", TIMESTAMP)?;
+    m.add("OPT_RFC2822", RFC2822)?;
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Bitflags accepted as the `option` argument to `loads()`/`dumps()`.
+//! Each `Opt` value is a single bit so callers can OR several together;
+//! the Python-facing `OPT_*` names are registered on the module in
+//! `lib.rs` and map 1:1 onto the constants below.
+
+pub type Opt = u16;
+
+pub const NAIVE_UTC: Opt = 1 << 1;
+pub const UTC_Z: Opt = 1 << 2;
+pub const OMIT_MICROSECONDS: Opt = 1 << 3;
+pub const PARSE_DATETIME: Opt = 1 << 4;
+pub const TIMESTAMP: Opt = 1 << 5;
+pub const RFC2822: Opt = 1 << 6;